@@ -0,0 +1,205 @@
+//! Combat: obstacles have health and die to sword hits, players have
+//! stamina that gates a dash, and players have their own health that an
+//! opposing player's spinning sword whittles down.
+
+use crate::{Key, Owner, Player, Source, Sword};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+/// Current/max hit points. Obstacles despawn and award score at zero.
+/// `Copy` so `netcode::add_netcode_plugins` can register it for rollback.
+#[derive(Component, Clone, Copy)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+}
+
+/// A player's combat resources. Stamina gates the sword spin and dash,
+/// regenerating over time. Mana is tracked for future ability work but
+/// isn't spent by anything yet. Health only goes down, via
+/// `damage_players_on_hit` below. `Copy` for the same rollback reason as
+/// `Health`.
+#[derive(Component, Clone, Copy)]
+pub struct Stats {
+    pub health: f32,
+    pub health_max: f32,
+    pub stamina: f32,
+    pub stamina_max: f32,
+    pub stamina_regen_per_sec: f32,
+    pub mana: f32,
+    pub mana_max: f32,
+    pub mana_regen_per_sec: f32,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            health: 100.0,
+            health_max: 100.0,
+            stamina: 5.0,
+            stamina_max: 5.0,
+            stamina_regen_per_sec: 1.0,
+            mana: 3.0,
+            mana_max: 3.0,
+            mana_regen_per_sec: 0.5,
+        }
+    }
+}
+
+/// How much damage a hit at a given sword angular speed deals.
+const DAMAGE_PER_ANGULAR_SPEED: f32 = 0.5;
+pub const SPIN_STAMINA_COST: f32 = 0.5;
+const DASH_STAMINA_COST: f32 = 1.0;
+const DASH_SPEED: f32 = 600.0;
+/// How many ticks a dash's burst velocity is protected from
+/// `player_movement`'s normal overwrite. Frame-counted, not time-based, so
+/// it stays in lockstep across GGRS peers.
+const DASH_SUPPRESS_TICKS: u8 = 8;
+
+/// Counts down while a dash burst should survive `player_movement`'s
+/// overwrite; zero means "not dashing". See `DASH_SUPPRESS_TICKS`.
+#[derive(Component, Clone, Copy, Default)]
+pub struct Dashing(pub u8);
+
+/// Total obstacles destroyed across all players this run.
+#[derive(Resource, Default)]
+pub struct Score(pub u32);
+
+/// Regenerates every player's stamina and mana over time. Runs before
+/// `sword_spin` and `player_dash` so a frame that both regenerates and
+/// spends stamina sees the up-to-date value.
+pub fn regen_stamina(time: Res<Time>, mut stats: Query<&mut Stats>) {
+    let dt = time.delta_secs();
+    for mut stats in &mut stats {
+        stats.stamina =
+            regen_clamped(stats.stamina, stats.stamina_regen_per_sec, stats.stamina_max, dt);
+        stats.mana = regen_clamped(stats.mana, stats.mana_regen_per_sec, stats.mana_max, dt);
+    }
+}
+
+/// Adds `rate * dt` to `current`, clamped to `max` - the regen formula
+/// shared by stamina and mana, factored out so it can be unit-tested
+/// without spinning up an ECS `App`.
+fn regen_clamped(current: f32, rate: f32, max: f32, dt: f32) -> f32 {
+    (current + rate * dt).min(max)
+}
+
+/// Dashes the player in their current movement direction when the owner's
+/// `Key::Dash` is pressed (or, on a touch device, a double-tap is
+/// registered) while moving and stamina allows it. A player who is not
+/// moving this frame does not dash.
+pub fn player_dash(
+    input_state: Res<crate::InputState>,
+    mut touch_state: ResMut<crate::TouchState>,
+    mut players: Query<(&Owner, &mut LinearVelocity, &mut Stats, &mut Dashing), With<Player>>,
+) {
+    let touch_dash_requested = touch_state.consume_double_tap();
+
+    for (owner, mut velocity, mut stats, mut dashing) in &mut players {
+        let dash_requested = input_state.just_pressed(owner.0, Key::Dash)
+            || (owner.0 == Source::KeyboardLeft && touch_dash_requested);
+
+        if !dash_requested {
+            continue;
+        }
+        if stats.stamina < DASH_STAMINA_COST {
+            continue;
+        }
+        if velocity.0.length() < 1.0 {
+            continue;
+        }
+
+        stats.stamina -= DASH_STAMINA_COST;
+        velocity.0 = velocity.0.normalize() * DASH_SPEED;
+        dashing.0 = DASH_SUPPRESS_TICKS;
+    }
+}
+
+/// Reads Avian collision-start events between swords and obstacles and
+/// applies damage proportional to the sword's current angular speed.
+pub fn damage_on_hit(
+    mut collisions: EventReader<CollisionStarted>,
+    swords: Query<&AngularVelocity, With<Sword>>,
+    mut healths: Query<&mut Health>,
+    mut score: ResMut<Score>,
+    mut commands: Commands,
+) {
+    for CollisionStarted(a, b) in collisions.read() {
+        for (sword_entity, obstacle_entity) in [(*a, *b), (*b, *a)] {
+            let Ok(angular_velocity) = swords.get(sword_entity) else {
+                continue;
+            };
+            let Ok(mut health) = healths.get_mut(obstacle_entity) else {
+                continue;
+            };
+
+            health.current -= damage_for_angular_speed(angular_velocity.0);
+
+            if health.current <= 0.0 {
+                commands.entity(obstacle_entity).despawn();
+                score.0 += 1;
+            }
+        }
+    }
+}
+
+/// Reads Avian collision-start events between swords and *opposing*
+/// players and applies damage to the struck player's `Stats::health`,
+/// proportional to the sword's current angular speed - same formula as
+/// `damage_on_hit`, but a sword never damages its own owner.
+pub fn damage_players_on_hit(
+    mut collisions: EventReader<CollisionStarted>,
+    swords: Query<(&Owner, &AngularVelocity), With<Sword>>,
+    mut players: Query<(&Owner, &mut Stats), With<Player>>,
+) {
+    for CollisionStarted(a, b) in collisions.read() {
+        for (sword_entity, player_entity) in [(*a, *b), (*b, *a)] {
+            let Ok((sword_owner, angular_velocity)) = swords.get(sword_entity) else {
+                continue;
+            };
+            let Ok((player_owner, mut stats)) = players.get_mut(player_entity) else {
+                continue;
+            };
+            if player_owner.0 == sword_owner.0 {
+                continue;
+            }
+
+            stats.health = (stats.health - damage_for_angular_speed(angular_velocity.0)).max(0.0);
+        }
+    }
+}
+
+/// How much damage a hit from a sword spinning at `angular_speed` deals -
+/// shared by `damage_on_hit` and `damage_players_on_hit`, factored out so
+/// it can be unit-tested without spinning up an ECS `App`.
+fn damage_for_angular_speed(angular_speed: f32) -> f32 {
+    angular_speed.abs() * DAMAGE_PER_ANGULAR_SPEED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_for_angular_speed_ignores_spin_direction() {
+        assert_eq!(damage_for_angular_speed(10.0), damage_for_angular_speed(-10.0));
+        assert_eq!(damage_for_angular_speed(10.0), 10.0 * DAMAGE_PER_ANGULAR_SPEED);
+    }
+
+    #[test]
+    fn test_regen_clamped_adds_then_caps_at_max() {
+        assert_eq!(regen_clamped(1.0, 2.0, 5.0, 1.0), 3.0);
+        assert_eq!(regen_clamped(4.5, 2.0, 5.0, 1.0), 5.0, "regen shouldn't overshoot the max");
+    }
+
+    #[test]
+    fn test_regen_clamped_holds_at_max() {
+        assert_eq!(regen_clamped(5.0, 2.0, 5.0, 1.0), 5.0);
+    }
+}
@@ -0,0 +1,306 @@
+//! "Juice": visual and audio feedback driven by collision events. Squash
+//! and stretch is always on since it only touches `Transform`/`Sprite`;
+//! the particle burst and procedural hit sound pull in `bevy_hanabi` and
+//! `fundsp` and live behind the `juice` feature so a minimal build doesn't
+//! need either.
+
+use crate::{Owner, Player};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+/// Relative speed (post-resolution) above which a collision counts as a
+/// "hard" impact worth spawning feedback for.
+const HARD_IMPACT_THRESHOLD: f32 = 150.0;
+
+/// How strongly a sprite squashes per unit of speed, and how quickly it
+/// springs back to its rest size.
+const SQUASH_STRETCH_FACTOR: f32 = 0.002;
+const SQUASH_STRETCH_SPRING: f32 = 10.0;
+
+/// The size this entity's sprite should relax back to. Set once at spawn
+/// time to whatever `custom_size` started as.
+#[derive(Component)]
+pub struct RestSize(pub Vec2);
+
+/// Stretches a sprite along its velocity direction and compresses it
+/// perpendicularly, then springs the scale back toward `RestSize` via a
+/// lerp. The stretch is applied per axis as `stretch^(cos^2 - sin^2)` of
+/// the velocity angle, so the two axis factors multiply to 1 and the
+/// sprite's area is preserved at every angle.
+pub fn squash_and_stretch(
+    time: Res<Time>,
+    mut query: Query<(&LinearVelocity, &RestSize, &mut Sprite)>,
+) {
+    for (velocity, rest_size, mut sprite) in &mut query {
+        let target_size = squash_and_stretch_target(velocity.0, rest_size.0);
+        let current = sprite.custom_size.unwrap_or(rest_size.0);
+        let k = (SQUASH_STRETCH_SPRING * time.delta_secs()).clamp(0.0, 1.0);
+        sprite.custom_size = Some(current.lerp(target_size, k));
+    }
+}
+
+/// The pure math behind `squash_and_stretch`: the sprite size this frame's
+/// `velocity` should stretch/squash `rest_size` toward, factored out so it
+/// can be unit-tested without spinning up an ECS `App`.
+fn squash_and_stretch_target(velocity: Vec2, rest_size: Vec2) -> Vec2 {
+    let speed = velocity.length();
+    if speed <= 1.0 {
+        return rest_size;
+    }
+
+    let stretch = 1.0 + speed * SQUASH_STRETCH_FACTOR;
+    let direction = velocity.normalize();
+    let cos2 = direction.x * direction.x;
+    let sin2 = direction.y * direction.y;
+    let exponent = cos2 - sin2;
+    Vec2::new(
+        rest_size.x * stretch.powf(exponent),
+        rest_size.y * stretch.powf(-exponent),
+    )
+}
+
+/// How strongly the sword's joint anchor trails the player's velocity, and
+/// the spring constants that make it lag behind and overshoot on a sudden
+/// direction change instead of just lerping to a target.
+const SWAY_FACTOR: f32 = 0.015;
+const SWAY_MAX_OFFSET: f32 = 12.0;
+const SWAY_STIFFNESS: f32 = 120.0;
+const SWAY_DAMPING: f32 = 9.0;
+
+/// Spring state for a sword's procedural sway. Its offset is written into
+/// the physics joint's anchor, so it moves the sword's body, not just its
+/// sprite; never read by `player_movement`/`sword_spin`.
+#[derive(Component, Default)]
+pub struct SwordSway {
+    offset: Vec2,
+    velocity: Vec2,
+}
+
+/// Springs the sword's joint anchor toward a target trailing the player's
+/// velocity, easing back to `Vec2::ZERO` once they stop.
+pub fn sword_sway(
+    time: Res<Time>,
+    players: Query<(&Owner, &LinearVelocity), With<Player>>,
+    mut joints: Query<(&Owner, &mut SwordSway, &mut RevoluteJoint)>,
+) {
+    let dt = time.delta_secs();
+    for (owner, mut sway, mut joint) in &mut joints {
+        let Some((_, velocity)) = players.iter().find(|(player_owner, _)| player_owner.0 == owner.0)
+        else {
+            continue;
+        };
+
+        let target = (-velocity.0 * SWAY_FACTOR).clamp_length_max(SWAY_MAX_OFFSET);
+        (sway.offset, sway.velocity) = step_sway_spring(sway.offset, sway.velocity, target, dt);
+
+        joint.local_anchor1 = sway.offset;
+    }
+}
+
+/// One semi-implicit Euler step of the underdamped spring driving
+/// `sword_sway`, factored out so it can be unit-tested without spinning up
+/// an ECS `App`. Returns the new `(offset, velocity)`.
+fn step_sway_spring(offset: Vec2, velocity: Vec2, target: Vec2, dt: f32) -> (Vec2, Vec2) {
+    let accel = (target - offset) * SWAY_STIFFNESS - velocity * SWAY_DAMPING;
+    let velocity = velocity + accel * dt;
+    let offset = offset + velocity * dt;
+    (offset, velocity)
+}
+
+/// A collision hard enough to warrant particles/sound/shake, with the
+/// midpoint between the two bodies' centers (an approximation of the
+/// contact point) and an impact strength in `[0, 1]` for scaling feedback.
+#[derive(Event)]
+pub struct HardImpact {
+    pub position: Vec2,
+    pub strength: f32,
+}
+
+/// Emits a `HardImpact` whenever a collision's relative speed clears
+/// `HARD_IMPACT_THRESHOLD`.
+pub fn detect_hard_impacts(
+    mut collisions: EventReader<CollisionStarted>,
+    velocities: Query<(&LinearVelocity, &Transform)>,
+    mut hard_impacts: EventWriter<HardImpact>,
+) {
+    for CollisionStarted(a, b) in collisions.read() {
+        let Ok((velocity_a, transform_a)) = velocities.get(*a) else {
+            continue;
+        };
+        let Ok((velocity_b, transform_b)) = velocities.get(*b) else {
+            continue;
+        };
+
+        let relative_speed = (velocity_a.0 - velocity_b.0).length();
+        if relative_speed < HARD_IMPACT_THRESHOLD {
+            continue;
+        }
+
+        let midpoint = (transform_a.translation.truncate() + transform_b.translation.truncate()) / 2.0;
+        let strength = (relative_speed / 1000.0).clamp(0.0, 1.0);
+        hard_impacts.send(HardImpact {
+            position: midpoint,
+            strength,
+        });
+    }
+}
+
+#[cfg(feature = "juice")]
+pub mod juice {
+    //! GPU particle bursts (`bevy_hanabi`) and procedurally synthesized
+    //! hit sounds (`fundsp`) for every `HardImpact`.
+    use super::HardImpact;
+    use bevy::prelude::*;
+    use bevy_hanabi::prelude::*;
+
+    /// The shared particle effect asset used for every impact burst; built
+    /// once at startup and instanced per-impact.
+    #[derive(Resource)]
+    pub struct ImpactEffect(pub Handle<EffectAsset>);
+
+    pub fn setup_impact_effect(mut commands: Commands, mut effects: ResMut<Assets<EffectAsset>>) {
+        let mut color_gradient = Gradient::new();
+        color_gradient.add_key(0.0, Vec4::new(1.0, 0.9, 0.4, 1.0));
+        color_gradient.add_key(1.0, Vec4::new(1.0, 0.9, 0.4, 0.0));
+
+        let writer = ExprWriter::new();
+        let age = writer.lit(0.0).expr();
+        let lifetime = writer.lit(0.3).expr();
+        let init_age = SetAttributeModifier::new(Attribute::AGE, age);
+        let init_lifetime = SetAttributeModifier::new(Attribute::LIFETIME, lifetime);
+
+        let effect = EffectAsset::new(64, Spawner::once(16.0.into(), true), writer.finish())
+            .with_name("impact_burst")
+            .init(init_age)
+            .init(init_lifetime)
+            .render(ColorOverLifetimeModifier {
+                gradient: color_gradient,
+            });
+
+        commands.insert_resource(ImpactEffect(effects.add(effect)));
+    }
+
+    /// Spawns a short-lived particle burst at the position of every
+    /// `HardImpact` this frame.
+    pub fn spawn_impact_particles(
+        mut commands: Commands,
+        mut hard_impacts: EventReader<HardImpact>,
+        impact_effect: Res<ImpactEffect>,
+    ) {
+        for impact in hard_impacts.read() {
+            commands.spawn((
+                ParticleEffectBundle {
+                    effect: ParticleEffect::new(impact_effect.0.clone())
+                        .with_spawner(Spawner::once((16.0 * impact.strength).into(), true)),
+                    transform: Transform::from_translation(impact.position.extend(1.0)),
+                    ..default()
+                },
+            ));
+        }
+    }
+
+    const SFX_SAMPLE_RATE: u32 = 44_100;
+    const SFX_DURATION_SECS: f32 = 0.08;
+
+    /// Synthesizes a short percussive hit sound, renders it to a WAV buffer,
+    /// and plays it immediately through `bevy_audio`.
+    pub fn play_impact_sfx(
+        mut commands: Commands,
+        mut hard_impacts: EventReader<HardImpact>,
+        mut audio_sources: ResMut<Assets<AudioSource>>,
+    ) {
+        use fundsp::hacker::*;
+
+        for impact in hard_impacts.read() {
+            let pitch = 220.0 + 660.0 * impact.strength;
+            let mut node = sine_hz(pitch)
+                * envelope(|t| if t < SFX_DURATION_SECS { 1.0 - t / SFX_DURATION_SECS } else { 0.0 });
+            node.set_sample_rate(SFX_SAMPLE_RATE as f64);
+
+            let sample_count = (SFX_SAMPLE_RATE as f32 * SFX_DURATION_SECS) as usize;
+            let samples: Vec<f32> = (0..sample_count).map(|_| node.get_mono()).collect();
+
+            let handle = audio_sources.add(AudioSource {
+                bytes: encode_wav_mono_16(&samples, SFX_SAMPLE_RATE).into(),
+            });
+            commands.spawn((AudioPlayer(handle), PlaybackSettings::DESPAWN));
+        }
+    }
+
+    /// Packs mono `f32` samples in `[-1, 1]` into an in-memory 16-bit PCM
+    /// WAV file.
+    fn encode_wav_mono_16(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+        let data_len = (samples.len() * 2) as u32;
+        let byte_rate = sample_rate * 2;
+
+        let mut bytes = Vec::with_capacity(44 + data_len as usize);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&byte_rate.to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes()); // block align
+        bytes.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            let pcm = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            bytes.extend_from_slice(&pcm.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_squash_and_stretch_target_preserves_area_on_axis() {
+        let rest_size = Vec2::new(30.0, 30.0);
+        let target = squash_and_stretch_target(Vec2::new(300.0, 0.0), rest_size);
+        assert!(
+            (target.x * target.y - rest_size.x * rest_size.y).abs() < 1e-3,
+            "axis-aligned motion should preserve area exactly, got {target:?}"
+        );
+    }
+
+    #[test]
+    fn test_squash_and_stretch_target_bounds_area_error_off_axis() {
+        let rest_size = Vec2::new(30.0, 30.0);
+        let rest_area = rest_size.x * rest_size.y;
+        let target = squash_and_stretch_target(Vec2::new(300.0, 300.0), rest_size);
+        let area_ratio = (target.x * target.y) / rest_area;
+        assert!(
+            area_ratio < 1.3,
+            "diagonal motion should only slightly over-stretch area, got ratio {area_ratio}"
+        );
+    }
+
+    #[test]
+    fn test_squash_and_stretch_target_holds_rest_size_below_speed_threshold() {
+        let rest_size = Vec2::new(30.0, 30.0);
+        assert_eq!(squash_and_stretch_target(Vec2::new(0.5, 0.0), rest_size), rest_size);
+    }
+
+    #[test]
+    fn test_step_sway_spring_moves_toward_target() {
+        let target = Vec2::new(10.0, 0.0);
+        let (offset, _) = step_sway_spring(Vec2::ZERO, Vec2::ZERO, target, 1.0 / 60.0);
+        assert!(offset.x > 0.0, "a step toward a positive target should move in that direction");
+    }
+
+    #[test]
+    fn test_step_sway_spring_settles_at_rest_target() {
+        let mut offset = Vec2::new(5.0, -3.0);
+        let mut velocity = Vec2::new(2.0, 1.0);
+        for _ in 0..500 {
+            (offset, velocity) = step_sway_spring(offset, velocity, Vec2::ZERO, 1.0 / 60.0);
+        }
+        assert!(offset.length() < 0.01, "spring should settle back to zero offset, got {offset:?}");
+    }
+}
@@ -0,0 +1,290 @@
+//! Game-state machine: menu -> playing -> win, plus level data and a
+//! persisted best-result save file so players can replay a level to beat
+//! their star rating.
+
+use crate::combat::Health;
+use crate::{spawn_player_sword_pair, Player, Source, Sword, SwordJoint};
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(States, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum AppState {
+    #[default]
+    Menu,
+    Playing,
+    Win,
+}
+
+pub type LevelId = u32;
+
+/// A level's obstacle layout. Replaces the old hardcoded
+/// `obstacle_positions` array with data that can vary per level.
+pub struct Level {
+    pub id: LevelId,
+    pub obstacle_positions: &'static [Vec2],
+}
+
+/// The built-in level list. A future asset-based loader can replace this
+/// table without touching anything that reads `CurrentLevel`.
+pub const LEVELS: &[Level] = &[
+    Level {
+        id: 1,
+        obstacle_positions: &[
+            Vec2::new(150.0, 100.0),
+            Vec2::new(-150.0, -100.0),
+            Vec2::new(200.0, -150.0),
+            Vec2::new(-200.0, 150.0),
+            Vec2::new(0.0, 200.0),
+        ],
+    },
+    Level {
+        id: 2,
+        obstacle_positions: &[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(250.0, 0.0),
+            Vec2::new(-250.0, 0.0),
+            Vec2::new(0.0, 220.0),
+            Vec2::new(0.0, -220.0),
+            Vec2::new(250.0, 220.0),
+        ],
+    },
+];
+
+fn level_by_id(id: LevelId) -> &'static Level {
+    LEVELS
+        .iter()
+        .find(|level| level.id == id)
+        .unwrap_or(&LEVELS[0])
+}
+
+/// Which level is currently loaded, if any. `setup` reads this to decide
+/// what to spawn; the menu writes it before entering `AppState::Playing`.
+#[derive(Resource, Default)]
+pub struct CurrentLevel(pub Option<LevelId>);
+
+/// Tracks how long the current level run has taken so far. The run's
+/// obstacles are tracked separately by the `LevelObstacle` marker, which
+/// `check_win_condition` queries to tell when the arena has been cleared.
+#[derive(Resource, Default)]
+pub struct LevelProgress {
+    pub elapsed: f32,
+}
+
+#[derive(Component)]
+pub struct LevelObstacle;
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct LevelResult {
+    pub best_time: f32,
+    pub stars: u8,
+}
+
+/// Best result per level, loaded from `save_file_path()` at startup and
+/// rewritten whenever a level is beaten in a new best time.
+#[derive(Resource, Default)]
+pub struct SaveData {
+    pub results: HashMap<LevelId, LevelResult>,
+}
+
+fn save_file_path() -> PathBuf {
+    PathBuf::from("sword_spinner_save.txt")
+}
+
+/// Format: one `level_id,best_time,stars` line per level. Deliberately
+/// plain text so the save file can be inspected/edited without tooling.
+pub fn load_save_data() -> SaveData {
+    let Ok(contents) = fs::read_to_string(save_file_path()) else {
+        return SaveData::default();
+    };
+    parse_save_data(&contents)
+}
+
+/// Parses the `level_id,best_time,stars` line format, skipping any line
+/// that doesn't split into exactly those three fields or fails to parse -
+/// a hand-edited save file should degrade gracefully, not crash the game.
+fn parse_save_data(contents: &str) -> SaveData {
+    let mut save = SaveData::default();
+
+    for line in contents.lines() {
+        let mut fields = line.split(',');
+        let (Some(id), Some(time), Some(stars)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if fields.next().is_some() {
+            continue;
+        }
+        if let (Ok(id), Ok(time), Ok(stars)) = (id.parse(), time.parse(), stars.parse()) {
+            save.results.insert(id, LevelResult { best_time: time, stars });
+        }
+    }
+
+    save
+}
+
+fn write_save_data(save: &SaveData) {
+    if let Err(err) = fs::write(save_file_path(), format_save_data(save)) {
+        warn!("failed to write save file: {err}");
+    }
+}
+
+fn format_save_data(save: &SaveData) -> String {
+    save.results
+        .iter()
+        .map(|(id, result)| format!("{},{},{}\n", id, result.best_time, result.stars))
+        .collect()
+}
+
+/// 1-3 stars based on how quickly the level was cleared. Tuned loosely
+/// against the built-in levels; a per-level par time could replace the
+/// flat thresholds later.
+fn stars_for_time(time: f32) -> u8 {
+    if time < 20.0 {
+        3
+    } else if time < 40.0 {
+        2
+    } else {
+        1
+    }
+}
+
+/// Level loader: spawns the player/sword pairs and the current level's
+/// obstacles. Runs on entering `AppState::Playing`.
+///
+/// When the `netcode` feature is active and an online session is
+/// configured, the local keyboard pairs are skipped - `netcode` spawns the
+/// networked pairs instead, see `netcode::spawn_network_players`.
+pub fn load_level(
+    mut commands: Commands,
+    current_level: Res<CurrentLevel>,
+    #[cfg(feature = "netcode")] netcode_config: Option<Res<crate::netcode::NetcodeConfig>>,
+) {
+    let level = level_by_id(current_level.0.unwrap_or(1));
+
+    #[cfg(feature = "netcode")]
+    let spawn_local_players = netcode_config.is_none();
+    #[cfg(not(feature = "netcode"))]
+    let spawn_local_players = true;
+
+    if spawn_local_players {
+        spawn_player_sword_pair(&mut commands, Source::KeyboardLeft, Vec2::new(-150.0, 0.0));
+        spawn_player_sword_pair(&mut commands, Source::KeyboardRight, Vec2::new(150.0, 0.0));
+    }
+
+    for pos in level.obstacle_positions {
+        commands.spawn((
+            LevelObstacle,
+            Health::new(10.0),
+            crate::feedback::RestSize(Vec2::new(30.0, 30.0)),
+            Sprite {
+                color: Color::srgb(0.8, 0.5, 0.2),
+                custom_size: Some(Vec2::new(30.0, 30.0)),
+                ..default()
+            },
+            Transform::from_xyz(pos.x, pos.y, 0.0),
+            RigidBody::Dynamic,
+            Collider::rectangle(30.0, 30.0),
+            LinearDamping(0.5),
+            AngularDamping(1.0),
+            Mass(1.0),
+        ));
+    }
+
+    commands.insert_resource(LevelProgress::default());
+}
+
+/// Despawns gameplay entities so `load_level` can start clean on a replay.
+pub fn teardown_level(
+    mut commands: Commands,
+    obstacles: Query<Entity, With<LevelObstacle>>,
+    players: Query<Entity, With<Player>>,
+    swords: Query<Entity, With<Sword>>,
+    joints: Query<Entity, With<SwordJoint>>,
+) {
+    for entity in &obstacles {
+        commands.entity(entity).despawn();
+    }
+    for entity in &players {
+        commands.entity(entity).despawn();
+    }
+    for entity in &swords {
+        commands.entity(entity).despawn();
+    }
+    for entity in &joints {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Win condition: every obstacle has been knocked out of the arena (or
+/// despawned by combat, see `combat::damage_on_hit`). Advances the
+/// clock otherwise.
+pub fn check_win_condition(
+    time: Res<Time>,
+    mut progress: ResMut<LevelProgress>,
+    obstacles: Query<&Transform, With<LevelObstacle>>,
+    current_level: Res<CurrentLevel>,
+    mut save: ResMut<SaveData>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    progress.elapsed += time.delta_secs();
+
+    let arena_half_width = 400.0;
+    let arena_half_height = 300.0;
+    let all_cleared = obstacles.iter().all(|transform| {
+        transform.translation.x.abs() > arena_half_width
+            || transform.translation.y.abs() > arena_half_height
+    });
+
+    if obstacles.is_empty() || all_cleared {
+        let level_id = current_level.0.unwrap_or(1);
+        let stars = stars_for_time(progress.elapsed);
+        let result = save.results.entry(level_id).or_default();
+        if result.best_time == 0.0 || progress.elapsed < result.best_time {
+            *result = LevelResult {
+                best_time: progress.elapsed,
+                stars,
+            };
+            write_save_data(&save);
+        }
+        next_state.set(AppState::Win);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stars_for_time_thresholds() {
+        assert_eq!(stars_for_time(19.9), 3, "under 20s should earn 3 stars");
+        assert_eq!(stars_for_time(20.0), 2, "20s is no longer under the 3-star threshold");
+        assert_eq!(stars_for_time(39.9), 2, "under 40s should earn 2 stars");
+        assert_eq!(stars_for_time(40.0), 1, "40s is no longer under the 2-star threshold");
+        assert_eq!(stars_for_time(120.0), 1, "a slow clear should still earn 1 star");
+    }
+
+    #[test]
+    fn test_save_data_round_trips_through_format_and_parse() {
+        let mut save = SaveData::default();
+        save.results.insert(1, LevelResult { best_time: 12.5, stars: 3 });
+        save.results.insert(2, LevelResult { best_time: 45.0, stars: 1 });
+
+        let parsed = parse_save_data(&format_save_data(&save));
+
+        assert_eq!(parsed.results.len(), 2);
+        assert_eq!(parsed.results[&1].best_time, 12.5);
+        assert_eq!(parsed.results[&1].stars, 3);
+        assert_eq!(parsed.results[&2].best_time, 45.0);
+        assert_eq!(parsed.results[&2].stars, 1);
+    }
+
+    #[test]
+    fn test_parse_save_data_skips_malformed_lines() {
+        let save = parse_save_data("1,12.5,3\nnot,a,valid,line\n2,oops,1\n\n");
+        assert_eq!(save.results.len(), 1, "only the well-formed line should parse");
+        assert_eq!(save.results[&1].best_time, 12.5);
+    }
+}
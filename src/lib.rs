@@ -1,11 +1,50 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
+use std::time::Instant;
+
+/// Virtual joystick radius, in world units, beyond which drag distance no
+/// longer increases the output magnitude.
+const JOYSTICK_MAX_RADIUS: f32 = 60.0;
+/// Drags shorter than this (world units) register as zero direction.
+const JOYSTICK_DEAD_ZONE: f32 = 10.0;
+/// A touch released within this distance and time of its start counts as
+/// a tap (spin trigger) rather than a drag.
+const TAP_DISTANCE_THRESHOLD: f32 = 15.0;
+const TAP_TIME_THRESHOLD: std::time::Duration = std::time::Duration::from_millis(200);
 
 // Resource to track touch input for mobile control
 #[derive(Resource, Default)]
 struct TouchState {
     direction: Vec2,
     is_active: bool,
+    /// World-space anchor recorded on touch-down; drag direction is
+    /// measured relative to this, not the raw screen position.
+    anchor_world: Option<Vec2>,
+    touch_start: Option<Instant>,
+    tap_spin_requested: bool,
+}
+
+impl TouchState {
+    fn consume_tap_spin(&mut self) -> bool {
+        let requested = self.tap_spin_requested;
+        self.tap_spin_requested = false;
+        requested
+    }
+}
+
+/// Pure helper behind the joystick math in `update_touch_state`, split out
+/// so the dead-zone/clamp behavior can be unit tested without standing up
+/// a full touch + camera ECS world.
+fn joystick_direction(anchor: Vec2, current: Vec2) -> Vec2 {
+    let offset = current - anchor;
+    let distance = offset.length();
+
+    if distance < JOYSTICK_DEAD_ZONE {
+        Vec2::ZERO
+    } else {
+        let clamped_distance = distance.min(JOYSTICK_MAX_RADIUS);
+        offset.normalize() * (clamped_distance / JOYSTICK_MAX_RADIUS)
+    }
 }
 
 /// Creates and configures the main App with all plugins and systems.
@@ -272,27 +311,54 @@ fn spin_button_interaction(
     }
 }
 
-/// Updates touch state resource for mobile control
-/// 
-/// **NOTE:** This is currently a placeholder implementation. Touch controls are disabled
-/// to isolate and fix the Android loading issue. Once the app loads successfully on Android,
-/// this will be properly implemented to calculate movement direction based on touch input.
+/// Drives a virtual joystick from touch input: the first touch-down
+/// anchors the stick, dragging sets `direction` relative to that anchor
+/// (clamped to `JOYSTICK_MAX_RADIUS`, dead-zoned below
+/// `JOYSTICK_DEAD_ZONE`), and a quick tap under `TAP_DISTANCE_THRESHOLD`/
+/// `TAP_TIME_THRESHOLD` requests a spin instead of a move.
 fn update_touch_state(
     mut touch_state: ResMut<TouchState>,
     touches: Option<Res<Touches>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
 ) {
-    if let Some(touches) = touches {
-        if let Some(_touch) = touches.first_pressed_position() {
-            // TODO: Calculate actual direction from touch position
-            // For now, just mark as active but don't move (placeholder)
-            touch_state.is_active = true;
-            touch_state.direction = Vec2::ZERO; // Will be calculated in future implementation
-        } else {
-            touch_state.is_active = false;
-            touch_state.direction = Vec2::ZERO;
+    let Some(touches) = touches else {
+        // No Touches resource available (e.g., in tests or desktop)
+        touch_state.is_active = false;
+        touch_state.direction = Vec2::ZERO;
+        return;
+    };
+
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+
+    let to_world = |screen_pos: Vec2| camera.viewport_to_world_2d(camera_transform, screen_pos).ok();
+
+    if let Some(touch) = touches.iter().next() {
+        let Some(current_world) = to_world(touch.position()) else {
+            return;
+        };
+
+        let anchor = *touch_state.anchor_world.get_or_insert(current_world);
+        if touch_state.touch_start.is_none() {
+            touch_state.touch_start = Some(Instant::now());
         }
+
+        touch_state.direction = joystick_direction(anchor, current_world);
+        touch_state.is_active = true;
     } else {
-        // No Touches resource available (e.g., in tests or desktop)
+        // Touch just released (or none this frame): check for a tap.
+        if let (Some(anchor), Some(start)) = (touch_state.anchor_world, touch_state.touch_start) {
+            let released_near_anchor = touch_state.direction == Vec2::ZERO
+                || touch_state.direction.length() * JOYSTICK_MAX_RADIUS < TAP_DISTANCE_THRESHOLD;
+            if released_near_anchor && start.elapsed() < TAP_TIME_THRESHOLD {
+                touch_state.tap_spin_requested = true;
+            }
+            let _ = anchor;
+        }
+
+        touch_state.anchor_world = None;
+        touch_state.touch_start = None;
         touch_state.is_active = false;
         touch_state.direction = Vec2::ZERO;
     }
@@ -340,10 +406,16 @@ fn player_movement(
 fn sword_spin(
     keyboard: Res<ButtonInput<KeyCode>>,
     mouse: Res<ButtonInput<MouseButton>>,
+    mut touch_state: ResMut<TouchState>,
     mut sword_query: Query<&mut AngularVelocity, With<Sword>>,
 ) {
-    // Desktop input only - mobile uses the button
-    if keyboard.just_pressed(KeyCode::Space) || mouse.just_pressed(MouseButton::Left) {
+    // Desktop input, the on-screen spin button, and a quick tap (handled
+    // in `update_touch_state`) all trigger the same impulse.
+    let should_spin = keyboard.just_pressed(KeyCode::Space)
+        || mouse.just_pressed(MouseButton::Left)
+        || touch_state.consume_tap_spin();
+
+    if should_spin {
         if let Ok(mut angular_velocity) = sword_query.get_single_mut() {
             angular_velocity.0 += 30.0; // Bigger impulse (15.0 -> 30.0)
         }
@@ -513,4 +585,25 @@ mod tests {
         let touch_state = app.world().resource::<TouchState>();
         assert!(!touch_state.is_active, "Touch should remain inactive with no input");
     }
+
+    #[test]
+    fn test_joystick_direction_dead_zone() {
+        let anchor = Vec2::new(100.0, 100.0);
+        assert_eq!(
+            joystick_direction(anchor, anchor + Vec2::new(2.0, 0.0)),
+            Vec2::ZERO,
+            "drags under the dead zone should register as zero"
+        );
+    }
+
+    #[test]
+    fn test_joystick_direction_clamps_to_max_radius() {
+        let anchor = Vec2::ZERO;
+        let far = anchor + Vec2::new(JOYSTICK_MAX_RADIUS * 4.0, 0.0);
+        let direction = joystick_direction(anchor, far);
+        assert!(
+            (direction.length() - 1.0).abs() < 1e-5,
+            "drags past the max radius should clamp to full magnitude"
+        );
+    }
 }
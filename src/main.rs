@@ -1,10 +1,28 @@
 use avian2d::prelude::*;
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
-use std::time::{Duration, Instant};
+use std::collections::HashSet;
+
+#[cfg(feature = "netcode")]
+mod netcode;
+
+mod combat;
+mod feedback;
+mod levels;
+
+use combat::{
+    damage_on_hit, damage_players_on_hit, player_dash, regen_stamina, Dashing, Health, Score,
+    Stats, SPIN_STAMINA_COST,
+};
+use feedback::{detect_hard_impacts, squash_and_stretch, sword_sway, HardImpact, RestSize, SwordSway};
+use levels::{
+    check_win_condition, load_level, load_save_data, teardown_level, AppState, CurrentLevel,
+};
 
 fn main() {
-    App::new()
-        .add_plugins((
+    let mut app = App::new();
+    app.add_plugins((
             DefaultPlugins.set(WindowPlugin {
                 primary_window: Some(Window {
                     title: "Sword Spinner".to_string(),
@@ -17,18 +35,182 @@ fn main() {
         ))
         .insert_resource(Gravity(Vec2::ZERO)) // Top-down game, no gravity
         .insert_resource(TouchState::default())
+        .insert_resource(InputState::default())
+        .insert_resource(FrameCount::default())
+        .add_systems(
+            Update,
+            tick_frame_count.run_if(in_state(AppState::Playing)),
+        )
+        .insert_resource(CurrentLevel::default())
+        .insert_resource(load_save_data())
+        .insert_resource(Score::default())
+        .init_state::<AppState>()
+        .add_event::<HardImpact>()
         .add_systems(Startup, setup)
+        .add_systems(OnEnter(AppState::Menu), spawn_menu_screen)
+        .add_systems(OnExit(AppState::Menu), despawn_menu_screen)
+        .add_systems(Update, menu_input.run_if(in_state(AppState::Menu)))
+        .add_systems(OnEnter(AppState::Playing), load_level)
+        .add_systems(OnExit(AppState::Playing), teardown_level)
+        .add_systems(OnEnter(AppState::Win), spawn_win_screen)
+        .add_systems(OnExit(AppState::Win), despawn_win_screen)
+        .add_systems(Update, win_screen_input.run_if(in_state(AppState::Win)))
         .add_systems(
+            Update,
+            (detect_double_tap, update_input_state)
+                .chain()
+                .run_if(in_state(AppState::Playing)),
+        );
+
+    // `spawn_gamepad_players` is never safe to run once netcode is active:
+    // a gamepad connecting mid-match would spawn a rollback-untracked
+    // entity that only exists on the peer it plugged into, breaking the
+    // determinism `netcode::add_netcode_plugins` depends on.
+    #[cfg(feature = "netcode")]
+    app.add_systems(
+        Update,
+        spawn_gamepad_players
+            .run_if(in_state(AppState::Playing))
+            .run_if(|config: Option<Res<netcode::NetcodeConfig>>| config.is_none()),
+    );
+    #[cfg(not(feature = "netcode"))]
+    app.add_systems(
+        Update,
+        spawn_gamepad_players.run_if(in_state(AppState::Playing)),
+    );
+
+    // Online play replaces the plain `Update` simulation with a fixed-tick
+    // GGRS rollback schedule; see `netcode::add_netcode_plugins`.
+    #[cfg(feature = "netcode")]
+    if let Some(config) = netcode::online_config_from_args() {
+        netcode::add_netcode_plugins(&mut app, config);
+    } else {
+        app.add_systems(
             Update,
             (
-                detect_double_tap,
+                regen_stamina,
                 player_movement,
+                player_dash,
                 sword_spin,
+                damage_on_hit,
+                damage_players_on_hit,
+                detect_hard_impacts,
+                squash_and_stretch,
+                sword_sway,
                 camera_follow,
+                camera_shake,
+                camera_zoom,
+                check_win_condition,
             )
-                .chain(),
+                .chain()
+                .run_if(in_state(AppState::Playing)),
+        );
+    }
+
+    #[cfg(not(feature = "netcode"))]
+    app.add_systems(
+        Update,
+        (
+            regen_stamina,
+            player_movement,
+            player_dash,
+            sword_spin,
+            damage_on_hit,
+            damage_players_on_hit,
+            detect_hard_impacts,
+            squash_and_stretch,
+            sword_sway,
+            camera_follow,
+            camera_shake,
+            camera_zoom,
+            check_win_condition,
         )
-        .run();
+            .chain()
+            .run_if(in_state(AppState::Playing)),
+    );
+
+    #[cfg(feature = "juice")]
+    app.add_systems(Startup, feedback::juice::setup_impact_effect)
+        .add_systems(
+            Update,
+            (feedback::juice::spawn_impact_particles, feedback::juice::play_impact_sfx)
+                .run_if(in_state(AppState::Playing)),
+        );
+
+    app.run();
+}
+
+#[derive(Component)]
+struct MenuScreen;
+
+#[derive(Component)]
+struct WinScreen;
+
+fn spawn_menu_screen(mut commands: Commands) {
+    commands.spawn((
+        MenuScreen,
+        Text::new("Sword Spinner\n\nPress Enter to start"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(40.0),
+            left: Val::Percent(35.0),
+            ..default()
+        },
+    ));
+}
+
+fn despawn_menu_screen(mut commands: Commands, query: Query<Entity, With<MenuScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn menu_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard.just_pressed(KeyCode::Enter) {
+        current_level.0 = Some(1);
+        next_state.set(AppState::Playing);
+    }
+}
+
+fn spawn_win_screen(mut commands: Commands, save: Res<levels::SaveData>, current_level: Res<CurrentLevel>) {
+    let result = current_level
+        .0
+        .and_then(|id| save.results.get(&id))
+        .copied()
+        .unwrap_or_default();
+    commands.spawn((
+        WinScreen,
+        Text::new(format!(
+            "Level cleared! Best time: {:.1}s  {}\n\nPress Enter to replay",
+            result.best_time,
+            "*".repeat(result.stars as usize),
+        )),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(40.0),
+            left: Val::Percent(30.0),
+            ..default()
+        },
+    ));
+}
+
+fn despawn_win_screen(mut commands: Commands, query: Query<Entity, With<WinScreen>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn win_screen_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard.just_pressed(KeyCode::Enter) {
+        next_state.set(AppState::Playing);
+    }
 }
 
 // Component markers
@@ -38,118 +220,181 @@ struct Player;
 #[derive(Component)]
 struct Sword;
 
+/// Tags the joint entity connecting a `Sword` to its `Player`, so
+/// `levels::teardown_level` can despawn it too instead of leaking a stale
+/// joint on every replay.
+#[derive(Component)]
+struct SwordJoint;
+
 #[derive(Component)]
 struct MainCamera;
 
+/// How many seconds ahead of the players' current velocity `camera_follow`
+/// biases its target, and how fast `camera_shake` burns off trauma.
+const LOOK_AHEAD_SECONDS: f32 = 0.3;
+const SHAKE_DECAY_PER_SEC: f32 = 1.5;
+
+// Tunable follow/zoom/shake settings for the MainCamera, kept on a
+// component instead of constants so a future per-level preset can swap
+// them without touching camera_follow/camera_shake/camera_zoom.
+#[derive(Component)]
+struct CameraController {
+    smoothing: f32,  // higher snaps to the target faster, lower trails more
+    look_ahead: f32, // max look-ahead bias from the players' average position
+    base_position: Vec2, // last smoothed, unshaken position; camera_shake offsets from this
+    zoom: f32,
+    zoom_min: f32,
+    zoom_max: f32,
+    zoom_speed: f32,
+    shake_trauma: f32,
+    shake_amplitude: f32,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            smoothing: 8.0,
+            look_ahead: 60.0,
+            base_position: Vec2::ZERO,
+            zoom: 1.0,
+            zoom_min: 0.5,
+            zoom_max: 2.0,
+            zoom_speed: 0.1,
+            shake_trauma: 0.0,
+            shake_amplitude: 16.0,
+        }
+    }
+}
+
+/// The device (or half-keyboard) driving a `Player`/`Sword` pair.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Source {
+    KeyboardLeft,
+    KeyboardRight,
+    Gamepad(Entity),
+    /// A networked peer, identified by its GGRS player handle. Only
+    /// produced by the optional `netcode` subsystem.
+    Online(usize),
+}
+
+/// Tags a `Player`/`Sword` entity with the `Source` that controls it.
+#[derive(Component, Clone, Copy)]
+struct Owner(Source);
+
+/// Abstract action a `Source` can be asked about, independent of the
+/// physical button/key/axis that produced it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Spin,
+    Dash,
+}
+
+/// Rebuilt every frame from keyboard, gamepad and touch input. Movement and
+/// spin only ever consult this set, never the raw device resources, so
+/// adding a new device just means teaching `update_input_state` about it.
+/// The touch double-tap dash is the exception: `combat::player_dash` reads
+/// it from `TouchState` directly.
+#[derive(Resource, Default)]
+struct InputState {
+    held: HashSet<(Source, Key)>,
+    just_pressed: HashSet<(Source, Key)>,
+}
+
+impl InputState {
+    fn held(&self, source: Source, key: Key) -> bool {
+        self.held.contains(&(source, key))
+    }
+
+    fn just_pressed(&self, source: Source, key: Key) -> bool {
+        self.just_pressed.contains(&(source, key))
+    }
+}
+
+/// Monotonic simulation frame counter. Anything that needs to reason about
+/// elapsed time in a way that must replay identically on both sides of a
+/// rollback (see `netcode`) should measure it in frames, not wall-clock
+/// time - `Instant::now()` differs between peers and would desync the
+/// double-tap detection below.
+#[derive(Resource, Default)]
+struct FrameCount(u64);
+
+fn tick_frame_count(mut frame_count: ResMut<FrameCount>) {
+    frame_count.0 += 1;
+}
+
 // Touch state resource for double-tap detection
 #[derive(Resource)]
 struct TouchState {
-    last_tap_time: Option<Instant>,
+    last_tap_frame: Option<u64>,
     last_tap_position: Option<Vec2>,
     double_tap_detected: bool,
-    double_tap_window: Duration,
+    double_tap_window_frames: u64,
     tap_distance_threshold: f32,
     touch_start_position: Option<Vec2>,
     is_dragging: bool,
     current_touch_position: Option<Vec2>, // Track current touch for movement
+    tap_spin_requested: bool,
 }
 
 impl Default for TouchState {
     fn default() -> Self {
         Self {
-            last_tap_time: None,
+            last_tap_frame: None,
             last_tap_position: None,
             double_tap_detected: false,
-            double_tap_window: Duration::from_millis(300),
+            double_tap_window_frames: 18, // ~300ms at the 60 fps sim rate
             tap_distance_threshold: 50.0,
             touch_start_position: None,
             is_dragging: false,
             current_touch_position: None,
+            tap_spin_requested: false,
         }
     }
 }
 
 impl TouchState {
-    fn register_tap(&mut self, position: Vec2) {
-        let now = Instant::now();
-        
+    fn register_tap(&mut self, position: Vec2, frame: u64) {
         // Check if this is a double-tap
-        if let (Some(last_time), Some(last_pos)) = (self.last_tap_time, self.last_tap_position) {
-            let time_diff = now.duration_since(last_time);
+        if let (Some(last_frame), Some(last_pos)) = (self.last_tap_frame, self.last_tap_position) {
+            let frame_diff = frame.saturating_sub(last_frame);
             let distance = position.distance(last_pos);
-            
-            if time_diff <= self.double_tap_window && distance <= self.tap_distance_threshold {
+
+            if frame_diff <= self.double_tap_window_frames && distance <= self.tap_distance_threshold {
                 self.double_tap_detected = true;
                 // Reset to prevent triple-tap
-                self.last_tap_time = None;
+                self.last_tap_frame = None;
                 self.last_tap_position = None;
                 return;
             }
         }
-        
-        self.last_tap_time = Some(now);
+
+        self.last_tap_frame = Some(frame);
         self.last_tap_position = Some(position);
     }
-    
+
     fn consume_double_tap(&mut self) -> bool {
         let detected = self.double_tap_detected;
         self.double_tap_detected = false;
         detected
     }
+
+    fn consume_tap_spin(&mut self) -> bool {
+        let requested = self.tap_spin_requested;
+        self.tap_spin_requested = false;
+        requested
+    }
 }
 
-// Setup system - initializes the game world
+// Setup system - initializes the permanent parts of the world (camera and
+// arena walls). Players and obstacles belong to a level and are spawned by
+// `levels::load_level` on entering `AppState::Playing` instead.
 fn setup(mut commands: Commands) {
     // Spawn camera
-    commands.spawn((Camera2d, MainCamera));
-
-    // Spawn player
-    let player_entity = commands
-        .spawn((
-            Player,
-            Sprite {
-                color: Color::srgb(0.2, 0.4, 0.8),
-                custom_size: Some(Vec2::new(40.0, 40.0)),
-                ..default()
-            },
-            Transform::from_xyz(0.0, 0.0, 0.0),
-            RigidBody::Dynamic,
-            Collider::rectangle(40.0, 40.0),
-            LockedAxes::ROTATION_LOCKED,
-            LinearVelocity::default(),
-            LinearDamping(2.0),
-            Mass(2.0),
-        ))
-        .id();
-
-    // Spawn sword
-    let sword_entity = commands
-        .spawn((
-            Sword,
-            Sprite {
-                color: Color::srgb(0.6, 0.6, 0.6),
-                custom_size: Some(Vec2::new(60.0, 10.0)),
-                ..default()
-            },
-            Transform::from_xyz(50.0, 0.0, 0.0),
-            RigidBody::Dynamic,
-            Collider::rectangle(60.0, 10.0),
-            AngularVelocity::default(),
-            LinearVelocity::default(),
-            LinearDamping(1.0),
-            AngularDamping(2.0),
-            Mass(0.5),
-        ))
-        .id();
-
-    // Create revolute joint connecting sword to player
-    // The sword rotates around the player at the player's center
-    commands.spawn(
-        RevoluteJoint::new(player_entity, sword_entity)
-            .with_local_anchor_1(Vec2::ZERO) // Player center
-            .with_local_anchor_2(Vec2::new(-25.0, 0.0)) // Offset from sword center
-            .with_compliance(0.00001), // Very stiff connection
-    );
+    commands.spawn((Camera2d, MainCamera, CameraController::default()));
 
     // Spawn arena boundaries
     let wall_thickness = 20.0;
@@ -203,30 +448,276 @@ fn setup(mut commands: Commands) {
         RigidBody::Static,
         Collider::rectangle(wall_thickness, arena_height),
     ));
+}
 
-    // Spawn some dynamic obstacles
-    let obstacle_positions = [
-        Vec2::new(150.0, 100.0),
-        Vec2::new(-150.0, -100.0),
-        Vec2::new(200.0, -150.0),
-        Vec2::new(-200.0, 150.0),
-        Vec2::new(0.0, 200.0),
-    ];
-
-    for pos in obstacle_positions.iter() {
-        commands.spawn((
+/// Spawns a `Player`/`Sword` pair owned by `source`, centered on `origin`,
+/// returning `(player, sword, joint)` - the joint entity is handed back too
+/// so callers that need to tag it further (e.g. `netcode::spawn_network_players`
+/// registering it for rollback) don't have to re-query for it.
+fn spawn_player_sword_pair(
+    commands: &mut Commands,
+    source: Source,
+    origin: Vec2,
+) -> (Entity, Entity, Entity) {
+    let player_entity = commands
+        .spawn((
+            Player,
+            Owner(source),
+            Stats::default(),
+            Dashing::default(),
+            RestSize(Vec2::new(40.0, 40.0)),
             Sprite {
-                color: Color::srgb(0.8, 0.5, 0.2),
-                custom_size: Some(Vec2::new(30.0, 30.0)),
+                color: Color::srgb(0.2, 0.4, 0.8),
+                custom_size: Some(Vec2::new(40.0, 40.0)),
                 ..default()
             },
-            Transform::from_xyz(pos.x, pos.y, 0.0),
+            Transform::from_xyz(origin.x, origin.y, 0.0),
             RigidBody::Dynamic,
-            Collider::rectangle(30.0, 30.0),
-            LinearDamping(0.5),
-            AngularDamping(1.0),
-            Mass(1.0),
-        ));
+            Collider::rectangle(40.0, 40.0),
+            LockedAxes::ROTATION_LOCKED,
+            LinearVelocity::default(),
+            LinearDamping(2.0),
+            Mass(2.0),
+        ))
+        .id();
+
+    let sword_entity = commands
+        .spawn((
+            Sword,
+            Owner(source),
+            RestSize(Vec2::new(60.0, 10.0)),
+            Sprite {
+                color: Color::srgb(0.6, 0.6, 0.6),
+                custom_size: Some(Vec2::new(60.0, 10.0)),
+                ..default()
+            },
+            Transform::from_xyz(origin.x + 50.0, origin.y, 0.0),
+            RigidBody::Dynamic,
+            Collider::rectangle(60.0, 10.0),
+            AngularVelocity::default(),
+            LinearVelocity::default(),
+            LinearDamping(1.0),
+            AngularDamping(2.0),
+            Mass(0.5),
+        ))
+        .id();
+
+    // Create revolute joint connecting sword to player
+    // The sword rotates around the player at the player's center
+    let joint_entity = commands
+        .spawn((
+            Owner(source),
+            SwordJoint,
+            SwordSway::default(),
+            RevoluteJoint::new(player_entity, sword_entity)
+                .with_local_anchor_1(Vec2::ZERO) // Player center
+                .with_local_anchor_2(Vec2::new(-25.0, 0.0)) // Offset from sword center
+                .with_compliance(0.00001), // Very stiff connection
+        ))
+        .id();
+
+    (player_entity, sword_entity, joint_entity)
+}
+
+/// Spawns a fresh `Player`/`Sword` pair for every gamepad that connects,
+/// so plugging in a controller mid-game drops a new fighter into the arena.
+fn spawn_gamepad_players(
+    mut commands: Commands,
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+) {
+    for event in connection_events.read() {
+        if let GamepadConnection::Connected { .. } = &event.connection {
+            spawn_player_sword_pair(&mut commands, Source::Gamepad(event.gamepad), Vec2::ZERO);
+        }
+    }
+}
+
+/// Rebuilds `InputState` from keyboard and gamepad devices and from the
+/// touch gestures `detect_double_tap` collected into `TouchState`. Gameplay
+/// systems never look at `ButtonInput`/`Gamepad` directly - this is the
+/// only place gameplay keyboard and gamepad polling happens.
+fn update_input_state(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut touch_state: ResMut<TouchState>,
+    left_player: Query<(&Owner, &Transform), With<Player>>,
+    mut input_state: ResMut<InputState>,
+) {
+    let previous_held = std::mem::take(&mut input_state.held);
+    input_state.just_pressed.clear();
+
+    let mut set = |source: Source, key: Key| {
+        input_state.held.insert((source, key));
+    };
+
+    if keyboard.pressed(KeyCode::KeyW) {
+        set(Source::KeyboardLeft, Key::Up);
+    }
+    if keyboard.pressed(KeyCode::KeyS) {
+        set(Source::KeyboardLeft, Key::Down);
+    }
+    if keyboard.pressed(KeyCode::KeyA) {
+        set(Source::KeyboardLeft, Key::Left);
+    }
+    if keyboard.pressed(KeyCode::KeyD) {
+        set(Source::KeyboardLeft, Key::Right);
+    }
+    if keyboard.pressed(KeyCode::Space) {
+        set(Source::KeyboardLeft, Key::Spin);
+    }
+    if keyboard.pressed(KeyCode::ShiftLeft) {
+        set(Source::KeyboardLeft, Key::Dash);
+    }
+
+    if keyboard.pressed(KeyCode::ArrowUp) {
+        set(Source::KeyboardRight, Key::Up);
+    }
+    if keyboard.pressed(KeyCode::ArrowDown) {
+        set(Source::KeyboardRight, Key::Down);
+    }
+    if keyboard.pressed(KeyCode::ArrowLeft) {
+        set(Source::KeyboardRight, Key::Left);
+    }
+    if keyboard.pressed(KeyCode::ArrowRight) {
+        set(Source::KeyboardRight, Key::Right);
+    }
+    if keyboard.pressed(KeyCode::Enter) {
+        set(Source::KeyboardRight, Key::Spin);
+    }
+    if keyboard.pressed(KeyCode::ShiftRight) {
+        set(Source::KeyboardRight, Key::Dash);
+    }
+
+    for (entity, gamepad) in &gamepads {
+        let source = Source::Gamepad(entity);
+        let stick = gamepad.left_stick();
+        if stick.y > 0.5 || gamepad.pressed(GamepadButton::DPadUp) {
+            set(source, Key::Up);
+        }
+        if stick.y < -0.5 || gamepad.pressed(GamepadButton::DPadDown) {
+            set(source, Key::Down);
+        }
+        if stick.x < -0.5 || gamepad.pressed(GamepadButton::DPadLeft) {
+            set(source, Key::Left);
+        }
+        if stick.x > 0.5 || gamepad.pressed(GamepadButton::DPadRight) {
+            set(source, Key::Right);
+        }
+        if gamepad.pressed(GamepadButton::South) {
+            set(source, Key::Spin);
+        }
+        if gamepad.pressed(GamepadButton::East) {
+            set(source, Key::Dash);
+        }
+    }
+
+    // Touch drag-to-move, folded into the same abstract key set as
+    // keyboard/gamepad: it always drives the `Source::KeyboardLeft` player
+    // (there's no second half to split on a touch device), thresholded
+    // into discrete directions the same way a gamepad stick is above.
+    if touch_state.is_dragging {
+        if let Some(world_pos) = touch_state.current_touch_position {
+            let left_player_pos = left_player
+                .iter()
+                .find(|(owner, _)| owner.0 == Source::KeyboardLeft)
+                .map(|(_, transform)| transform.translation.truncate());
+
+            if let Some(player_pos) = left_player_pos {
+                let target_direction = world_pos - player_pos;
+                if target_direction.length() > 20.0 {
+                    let stick = target_direction.normalize();
+                    if stick.y > 0.5 {
+                        set(Source::KeyboardLeft, Key::Up);
+                    } else if stick.y < -0.5 {
+                        set(Source::KeyboardLeft, Key::Down);
+                    }
+                    if stick.x > 0.5 {
+                        set(Source::KeyboardLeft, Key::Right);
+                    } else if stick.x < -0.5 {
+                        set(Source::KeyboardLeft, Key::Left);
+                    }
+                }
+            }
+        }
+    }
+
+    for held in &input_state.held {
+        if !previous_held.contains(held) {
+            input_state.just_pressed.insert(*held);
+        }
+    }
+
+    // A plain tap (not a drag) spins the sword, the touch equivalent of
+    // tapping Space - double-tap still triggers the dash via
+    // `consume_double_tap` in `combat::player_dash`.
+    if touch_state.consume_tap_spin() {
+        input_state.just_pressed.insert((Source::KeyboardLeft, Key::Spin));
+    }
+}
+
+// System to handle player movement. Every owner - keyboard half, gamepad,
+// or touch - is abstracted into the same `InputState` query by this point.
+fn player_movement(
+    mut player_query: Query<(&Owner, &mut LinearVelocity, &mut Dashing), With<Player>>,
+    input_state: Res<InputState>,
+) {
+    for (owner, mut velocity, mut dashing) in &mut player_query {
+        if dashing.0 > 0 {
+            dashing.0 -= 1;
+            continue;
+        }
+
+        let mut direction = Vec2::ZERO;
+
+        if input_state.held(owner.0, Key::Up) {
+            direction.y += 1.0;
+        }
+        if input_state.held(owner.0, Key::Down) {
+            direction.y -= 1.0;
+        }
+        if input_state.held(owner.0, Key::Left) {
+            direction.x -= 1.0;
+        }
+        if input_state.held(owner.0, Key::Right) {
+            direction.x += 1.0;
+        }
+
+        // Normalize and apply velocity
+        if direction.length() > 0.0 {
+            direction = direction.normalize();
+            velocity.0 = direction * 200.0; // Movement speed
+        } else {
+            velocity.0 = Vec2::ZERO;
+        }
+    }
+}
+
+// System to spin the sword
+/// Spins each sword on the owner's `Key::Spin` press, gated by the
+/// matching player's stamina (double-tap now drives the dash instead, see
+/// `combat::player_dash`).
+fn sword_spin(
+    input_state: Res<InputState>,
+    mut players: Query<(&Owner, &mut Stats), With<Player>>,
+    mut sword_query: Query<(&Owner, &mut AngularVelocity), With<Sword>>,
+) {
+    for (owner, mut angular_velocity) in &mut sword_query {
+        if !input_state.just_pressed(owner.0, Key::Spin) {
+            continue;
+        }
+
+        let Some((_, mut stats)) = players
+            .iter_mut()
+            .find(|(player_owner, _)| player_owner.0 == owner.0)
+        else {
+            continue;
+        };
+        if stats.stamina < SPIN_STAMINA_COST {
+            continue;
+        }
+
+        stats.stamina -= SPIN_STAMINA_COST;
+        angular_velocity.0 += 15.0; // Apply spin force
     }
 }
 
@@ -235,6 +726,7 @@ fn detect_double_tap(
     mut touch_events: EventReader<bevy::input::touch::TouchInput>,
     mut touch_state: ResMut<TouchState>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    frame_count: Res<FrameCount>,
 ) {
     let (camera, camera_transform) = camera_query.single();
 
@@ -252,7 +744,7 @@ fn detect_double_tap(
             if let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, touch.position)
             {
                 touch_state.current_touch_position = Some(world_pos);
-                
+
                 // Check if this is a drag (moved more than threshold)
                 if let Some(start_pos) = touch_state.touch_start_position {
                     let distance = world_pos.distance(start_pos);
@@ -268,7 +760,11 @@ fn detect_double_tap(
                 if let Ok(world_pos) =
                     camera.viewport_to_world_2d(camera_transform, touch.position)
                 {
-                    touch_state.register_tap(world_pos);
+                    touch_state.register_tap(world_pos, frame_count.0);
+                    // Every completed tap also requests a sword spin, same as
+                    // tapping Space would - double-tap triggers the dash too,
+                    // via `consume_double_tap` in `combat::player_dash`.
+                    touch_state.tap_spin_requested = true;
                 }
             }
             touch_state.touch_start_position = None;
@@ -278,86 +774,85 @@ fn detect_double_tap(
     }
 }
 
-// System to handle player movement (keyboard and touch)
-fn player_movement(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mut player_query: Query<(&Transform, &mut LinearVelocity), With<Player>>,
-    touch_state: Res<TouchState>,
+// Follows the players' average position, biased a little ahead of their
+// average velocity. Writes base_position rather than the Transform
+// directly so camera_shake has an unshaken position to offset from.
+fn camera_follow(
+    time: Res<Time>,
+    player_query: Query<(&Transform, &LinearVelocity), With<Player>>,
+    mut camera_query: Query<(&mut Transform, &mut CameraController), (With<MainCamera>, Without<Player>)>,
 ) {
-    let (player_transform, mut velocity) = player_query.single_mut();
-    let mut direction = Vec2::ZERO;
-
-    // Keyboard input for desktop
-    if keyboard.pressed(KeyCode::KeyW) || keyboard.pressed(KeyCode::ArrowUp) {
-        direction.y += 1.0;
-    }
-    if keyboard.pressed(KeyCode::KeyS) || keyboard.pressed(KeyCode::ArrowDown) {
-        direction.y -= 1.0;
-    }
-    if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
-        direction.x -= 1.0;
-    }
-    if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
-        direction.x += 1.0;
+    let count = player_query.iter().len() as f32;
+    if count == 0.0 {
+        return;
     }
 
-    // Touch input for mobile (drag to move)
-    if touch_state.is_dragging {
-        if let Some(world_pos) = touch_state.current_touch_position {
-            // Calculate direction from player to touch position
-            let target_direction = world_pos - player_transform.translation.truncate();
-            
-            // Only move if touch is reasonably far from player
-            if target_direction.length() > 20.0 {
-                direction = target_direction;
-            }
-        }
-    }
+    let Ok((mut camera_transform, mut controller)) = camera_query.get_single_mut() else {
+        return;
+    };
 
-    // Normalize and apply velocity
-    if direction.length() > 0.0 {
-        direction = direction.normalize();
-        velocity.0 = direction * 200.0; // Movement speed
-    } else {
-        velocity.0 = Vec2::ZERO;
-    }
+    let (position_sum, velocity_sum) = player_query.iter().fold(
+        (Vec2::ZERO, Vec2::ZERO),
+        |(pos_acc, vel_acc), (transform, velocity)| {
+            (pos_acc + transform.translation.truncate(), vel_acc + velocity.0)
+        },
+    );
+    let average_pos = position_sum / count;
+    let average_vel = velocity_sum / count;
+
+    let look_ahead_offset = (average_vel * LOOK_AHEAD_SECONDS).clamp_length_max(controller.look_ahead);
+    let target = average_pos + look_ahead_offset;
+
+    let k = (controller.smoothing * time.delta_secs()).clamp(0.0, 1.0);
+    controller.base_position = controller.base_position.lerp(target, k);
+
+    camera_transform.translation.x = controller.base_position.x;
+    camera_transform.translation.y = controller.base_position.y;
 }
 
-// System to spin the sword
-fn sword_spin(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    mouse: Res<ButtonInput<MouseButton>>,
-    mut touch_state: ResMut<TouchState>,
-    mut sword_query: Query<&mut AngularVelocity, With<Sword>>,
+// Hard impacts (any collision `detect_hard_impacts` reports, not only
+// sword hits) add trauma, which decays every frame while driving a
+// small jittered offset on top of camera_follow's base_position.
+fn camera_shake(
+    time: Res<Time>,
+    mut hard_impacts: EventReader<HardImpact>,
+    mut camera_query: Query<(&mut Transform, &mut CameraController), With<MainCamera>>,
 ) {
-    let mut should_spin = false;
+    let Ok((mut camera_transform, mut controller)) = camera_query.get_single_mut() else {
+        return;
+    };
 
-    // Desktop input
-    if keyboard.just_pressed(KeyCode::Space) || mouse.just_pressed(MouseButton::Left) {
-        should_spin = true;
+    for impact in hard_impacts.read() {
+        controller.shake_trauma = (controller.shake_trauma + impact.strength).min(1.0);
     }
 
-    // Mobile input - double-tap
-    if touch_state.consume_double_tap() {
-        should_spin = true;
+    if controller.shake_trauma > 0.0 {
+        let elapsed = time.elapsed_secs();
+        let jitter = Vec2::new((elapsed * 53.0).sin(), (elapsed * 37.0).cos());
+        let offset = jitter * controller.shake_trauma.powi(2) * controller.shake_amplitude;
+        camera_transform.translation.x = controller.base_position.x + offset.x;
+        camera_transform.translation.y = controller.base_position.y + offset.y;
     }
 
-    if should_spin {
-        if let Ok(mut angular_velocity) = sword_query.get_single_mut() {
-            angular_velocity.0 += 15.0; // Apply spin force
-        }
-    }
+    controller.shake_trauma =
+        (controller.shake_trauma - SHAKE_DECAY_PER_SEC * time.delta_secs()).max(0.0);
 }
 
-// System to make camera follow the player
-fn camera_follow(
-    player_query: Query<&Transform, With<Player>>,
-    mut camera_query: Query<&mut Transform, (With<MainCamera>, Without<Player>)>,
+/// Mouse-wheel zoom, scaling the orthographic projection and clamped to the
+/// controller's configured bounds. A touch/pinch backend could drive the
+/// same `controller.zoom` field without touching this system.
+fn camera_zoom(
+    mut wheel_events: EventReader<MouseWheel>,
+    mut camera_query: Query<(&mut OrthographicProjection, &mut CameraController), With<MainCamera>>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-            camera_transform.translation.x = player_transform.translation.x;
-            camera_transform.translation.y = player_transform.translation.y;
-        }
+    let Ok((mut projection, mut controller)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    for event in wheel_events.read() {
+        controller.zoom =
+            (controller.zoom - event.y * controller.zoom_speed).clamp(controller.zoom_min, controller.zoom_max);
     }
+
+    projection.scale = controller.zoom;
 }
@@ -0,0 +1,277 @@
+//! Optional online 2-player versus mode built on GGRS-style P2P rollback.
+//!
+//! Disabled by default; enable the `netcode` feature to compile this module
+//! in and route the simulation through [`add_netcode_plugins`] instead of
+//! the plain `Update` chain. Determinism is the whole point here: the
+//! gameplay systems only run on confirmed/predicted GGRS frames, and
+//! anything that feeds the simulation (including obstacle spawn seeds) must
+//! agree byte-for-byte between peers. The Avian physics step is not moved
+//! into the GGRS schedule; it keeps running on Avian's own schedule.
+#![cfg(feature = "netcode")]
+
+use crate::combat::{
+    damage_on_hit, damage_players_on_hit, player_dash, regen_stamina, Dashing, Health, Stats,
+};
+use crate::feedback::{detect_hard_impacts, squash_and_stretch, sword_sway};
+use crate::levels::{check_win_condition, AppState};
+use crate::{spawn_player_sword_pair, sword_spin, InputState, Key, Source, Sword};
+use avian2d::prelude::{AngularVelocity, LinearVelocity, PhysicsSchedule};
+use bevy::prelude::*;
+use bevy_ggrs::ggrs::{self, PlayerType, SessionBuilder, UdpNonBlockingSocket};
+use bevy_ggrs::{
+    AddRollbackCommandExtension, GgrsApp, GgrsPlugin, GgrsSchedule, LocalInputs, LocalPlayers,
+    PlayerInputs,
+};
+use bytemuck::{Pod, Zeroable};
+use std::net::SocketAddr;
+
+const INPUT_UP: u8 = 1 << 0;
+const INPUT_DOWN: u8 = 1 << 1;
+const INPUT_LEFT: u8 = 1 << 2;
+const INPUT_RIGHT: u8 = 1 << 3;
+const INPUT_SPIN: u8 = 1 << 4;
+
+/// The GGRS config for this game: one frame of input is a packed bit-flag
+/// byte, small enough to go over UDP with plenty of room to spare.
+pub struct Config;
+
+impl ggrs::Config for Config {
+    type Input = BoxInput;
+    type State = u8;
+    type Address = SocketAddr;
+}
+
+/// A single player's input for a single frame, packed for rollback
+/// snapshotting and wire transfer.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Pod, Zeroable, Debug, Default)]
+pub struct BoxInput {
+    pub buttons: u8,
+}
+
+/// How to reach the remote peer(s) and how aggressively to predict ahead
+/// of confirmed input.
+#[derive(Resource, Clone)]
+pub struct NetcodeConfig {
+    pub local_port: u16,
+    pub remote_addresses: Vec<SocketAddr>,
+    pub input_delay: usize,
+    pub max_prediction_window: usize,
+}
+
+impl Default for NetcodeConfig {
+    fn default() -> Self {
+        Self {
+            local_port: 7777,
+            remote_addresses: Vec::new(),
+            input_delay: 2,
+            max_prediction_window: 12,
+        }
+    }
+}
+
+/// Tags the `Player`/`Sword` pair controlled by GGRS player `handle`.
+#[derive(Component, Clone, Copy)]
+pub struct NetworkPlayer {
+    pub handle: usize,
+}
+
+/// Parses `--online --port <local> --peer <addr>` (repeatable) from the
+/// process args into a `NetcodeConfig`, or returns `None` to stay in the
+/// local/offline mode.
+pub fn online_config_from_args() -> Option<NetcodeConfig> {
+    let args: Vec<String> = std::env::args().collect();
+    if !args.iter().any(|a| a == "--online") {
+        return None;
+    }
+
+    let mut config = NetcodeConfig::default();
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--port" => {
+                if let Some(port) = iter.next().and_then(|p| p.parse().ok()) {
+                    config.local_port = port;
+                }
+            }
+            "--peer" => {
+                if let Some(addr) = iter.next().and_then(|p| p.parse().ok()) {
+                    config.remote_addresses.push(addr);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(config)
+}
+
+/// Builds a `P2PSession` over a non-blocking UDP socket bound to
+/// `config.local_port`, with one local player and one remote player per
+/// address in `config.remote_addresses`.
+pub fn build_p2p_session(
+    config: &NetcodeConfig,
+) -> Result<ggrs::P2PSession<Config>, ggrs::GgrsError> {
+    let mut builder = SessionBuilder::<Config>::new()
+        .with_num_players(1 + config.remote_addresses.len())
+        .with_input_delay(config.input_delay)
+        .with_max_prediction_window(config.max_prediction_window)?;
+
+    builder = builder.add_player(PlayerType::Local, 0)?;
+    for (i, addr) in config.remote_addresses.iter().enumerate() {
+        builder = builder.add_player(PlayerType::Remote(*addr), i + 1)?;
+    }
+
+    let socket = UdpNonBlockingSocket::bind_to_port(config.local_port)
+        .map_err(|_| ggrs::GgrsError::InvalidRequest {
+            info: "failed to bind local UDP socket".to_string(),
+        })?;
+
+    builder.start_p2p_session(socket)
+}
+
+/// Packs this frame's local input from `InputState` into a `BoxInput` for
+/// every local GGRS handle, as required by `bevy_ggrs::ReadInputs`. The
+/// local handle is always driven by the physical `Source::KeyboardLeft`
+/// half of the keyboard (the same device `update_input_state` already
+/// fills in every frame, online or off) - `Source::Online` isn't a real
+/// input-producing device, it's only what `apply_ggrs_inputs` tags
+/// decoded input with afterwards.
+fn read_local_inputs(
+    mut commands: Commands,
+    input_state: Res<InputState>,
+    local_players: Res<LocalPlayers>,
+) {
+    let mut local_inputs = std::collections::HashMap::new();
+
+    let mut buttons = 0u8;
+    if input_state.held(Source::KeyboardLeft, Key::Up) {
+        buttons |= INPUT_UP;
+    }
+    if input_state.held(Source::KeyboardLeft, Key::Down) {
+        buttons |= INPUT_DOWN;
+    }
+    if input_state.held(Source::KeyboardLeft, Key::Left) {
+        buttons |= INPUT_LEFT;
+    }
+    if input_state.held(Source::KeyboardLeft, Key::Right) {
+        buttons |= INPUT_RIGHT;
+    }
+    if input_state.just_pressed(Source::KeyboardLeft, Key::Spin) {
+        buttons |= INPUT_SPIN;
+    }
+
+    for handle in &local_players.0 {
+        local_inputs.insert(*handle, BoxInput { buttons });
+    }
+
+    commands.insert_resource(LocalInputs::<Config>(local_inputs));
+}
+
+/// Decodes each confirmed/predicted `BoxInput` back into `InputState` so
+/// `player_movement`/`sword_spin` can run unmodified inside the rollback
+/// schedule.
+fn apply_ggrs_inputs(
+    inputs: Res<PlayerInputs<Config>>,
+    players: Query<&NetworkPlayer>,
+    mut input_state: ResMut<InputState>,
+) {
+    for network_player in &players {
+        let source = Source::Online(network_player.handle);
+        let (input, _status) = inputs[network_player.handle];
+
+        for (bit, key) in [
+            (INPUT_UP, Key::Up),
+            (INPUT_DOWN, Key::Down),
+            (INPUT_LEFT, Key::Left),
+            (INPUT_RIGHT, Key::Right),
+        ] {
+            if input.buttons & bit != 0 {
+                input_state.held.insert((source, key));
+            }
+        }
+        if input.buttons & INPUT_SPIN != 0 {
+            input_state.just_pressed.insert((source, Key::Spin));
+        }
+    }
+}
+
+fn clear_input_state(mut input_state: ResMut<InputState>) {
+    input_state.held.clear();
+    input_state.just_pressed.clear();
+}
+
+/// Spawns one `Player`/`Sword`/`RevoluteJoint` set per GGRS handle (handle
+/// 0 is always local; one more per configured remote peer), each tagged
+/// with the `NetworkPlayer` that routes its decoded input and with
+/// `Rollback` so `bevy_ggrs` actually snapshots and restores it - without
+/// this, the rollback component types registered in `add_netcode_plugins`
+/// have nothing tagged to apply to.
+fn spawn_network_players(mut commands: Commands, config: Res<NetcodeConfig>) {
+    let num_players = 1 + config.remote_addresses.len();
+    for handle in 0..num_players {
+        let origin = Vec2::new(if handle == 0 { -150.0 } else { 150.0 }, 0.0);
+        let (player, sword, joint) =
+            spawn_player_sword_pair(&mut commands, Source::Online(handle), origin);
+        commands.entity(player).insert(NetworkPlayer { handle }).add_rollback();
+        commands.entity(sword).insert(NetworkPlayer { handle }).add_rollback();
+        commands.entity(joint).add_rollback();
+    }
+}
+
+/// Wires the GGRS plugin into `app`, registers every rollback-relevant
+/// component, and runs the *entire* simulation - input, movement, combat,
+/// juice, camera, and win detection, the same systems `main()` chains into
+/// `Update` for offline play - inside a fixed 60 Hz `GgrsSchedule` instead
+/// of `Update`. Call this instead of scheduling those systems normally.
+///
+/// Nothing non-deterministic may run here: that's also why `main()` gates
+/// `spawn_gamepad_players` off whenever this is active, since a gamepad
+/// connecting mid-match would spawn a rollback-untracked entity only one
+/// peer knows about.
+pub fn add_netcode_plugins(app: &mut App, config: NetcodeConfig) {
+    app.add_plugins(GgrsPlugin::<Config>::default())
+        .set_rollback_schedule_fps(60)
+        .rollback_component_with_copy::<Transform>()
+        .rollback_component_with_copy::<LinearVelocity>()
+        .rollback_component_with_copy::<AngularVelocity>()
+        .rollback_component_with_copy::<Stats>()
+        .rollback_component_with_copy::<Health>()
+        .rollback_component_with_copy::<Dashing>()
+        .add_systems(ReadInputs, read_local_inputs)
+        .add_systems(OnEnter(AppState::Playing), spawn_network_players)
+        .add_systems(
+            GgrsSchedule,
+            (
+                clear_input_state,
+                apply_ggrs_inputs,
+                regen_stamina,
+                crate::player_movement,
+                player_dash,
+                sword_spin,
+                damage_on_hit,
+                damage_players_on_hit,
+                detect_hard_impacts,
+                squash_and_stretch,
+                sword_sway,
+                crate::camera_follow,
+                crate::camera_shake,
+                crate::camera_zoom,
+                check_win_condition,
+            )
+                .chain()
+                .before(PhysicsSchedule),
+        )
+        .insert_resource(config.clone());
+
+    match build_p2p_session(&config) {
+        Ok(session) => {
+            app.insert_resource(bevy_ggrs::Session::P2P(session));
+        }
+        Err(err) => {
+            warn!("netcode: failed to start P2P session: {err:?}");
+        }
+    }
+}
+
+use bevy_ggrs::ReadInputs;